@@ -12,14 +12,30 @@
 //! constructing a `ZkSolcConfig` instance in a flexible and convenient manner.
 use foundry_compilers::{
     artifacts::{
-        output_selection::OutputSelection, serde_helpers, Libraries, OptimizerDetails,
-        SettingsMetadata, Source,
+        output_selection::{ContractOutputSelection, OutputSelection},
+        serde_helpers, BytecodeHash, Libraries, OptimizerDetails, SettingsMetadata, Source,
     },
     remappings::Remapping,
 };
+use semver::Version;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+/// The `zksolc` version that dropped the standalone `isSystem` flag in favor of the more
+/// general `enableEraVMExtensions` flag.
+pub const ERAVM_EXTENSIONS_MINIMUM_VERSION: Version = Version::new(1, 5, 0);
+
+/// The minimal set of output selectors needed to produce usable bytecode and an ABI, i.e.
+/// everything [`Settings::prune_output_selection`] keeps by default.
+pub const MINIMAL_OUTPUT_SELECTION: &[&str] = &[
+    "abi",
+    "evm.bytecode.object",
+    "evm.bytecode.sourceMap",
+    "evm.deployedBytecode.object",
+    "evm.deployedBytecode.sourceMap",
+    "evm.methodIdentifiers",
+];
+
 const SOLIDITY: &str = "Solidity";
 /// Configuration for the zkSolc compiler.
 ///
@@ -70,7 +86,17 @@ pub struct Settings {
     #[serde(default)]
     pub libraries: Libraries,
     /// A flag indicating whether to enable the system contract compilation mode.
+    ///
+    /// Deprecated by zksolc in favor of [`Settings::enable_eravm_extensions`] starting with
+    /// [`ERAVM_EXTENSIONS_MINIMUM_VERSION`]; kept here so older configs keep working, and mapped
+    /// onto the flag the target compiler version actually understands by
+    /// [`Settings::normalized_for_version`].
     pub is_system: bool,
+    /// A flag indicating whether to enable the EraVM assembly extensions (e.g. far calls,
+    /// low-level system mode opcodes). This supersedes `is_system` starting with
+    /// [`ERAVM_EXTENSIONS_MINIMUM_VERSION`].
+    #[serde(default, rename = "enableEraVMExtensions")]
+    pub enable_eravm_extensions: bool,
     /// A flag indicating whether to forcibly switch to the EVM legacy assembly pipeline.
     pub force_evmla: bool,
     /// Path to cache missing library dependencies, used for compiling and deploying libraries.
@@ -83,6 +109,108 @@ pub struct Settings {
     /// List of specific contracts to be compiled.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub contracts_to_compile: Vec<String>,
+    /// Warnings to suppress, e.g. `txorigin` to allow usage of `tx.origin` without a warning.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressed_warnings: Vec<String>,
+    /// Errors to suppress, e.g. `sendtransfer` to allow usage of `.send`/`.transfer` without
+    /// the compiler erroring out.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressed_errors: Vec<String>,
+    /// Catch-all for zksolc settings that this struct does not (yet) model explicitly.
+    ///
+    /// zksolc evolves faster than this struct can track, so any key placed here is passed
+    /// through verbatim to the compiler, both on serialization and deserialization. When
+    /// deserializing, keys that match one of the typed fields above go to that field and never
+    /// land here; a matching key inserted here directly is serialized in addition to the typed
+    /// field's own key, not dropped.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The set of warning identifiers that `zksolc` currently understands for
+/// `suppressedWarnings`.
+pub const SUPPORTED_SUPPRESSED_WARNINGS: &[&str] = &["txorigin"];
+
+/// The set of error identifiers that `zksolc` currently understands for
+/// `suppressedErrors`.
+pub const SUPPORTED_SUPPRESSED_ERRORS: &[&str] = &["sendtransfer"];
+
+impl Settings {
+    /// Validates that `suppressed_warnings` and `suppressed_errors` only contain identifiers
+    /// that `zksolc` currently supports, so that typos surface as a config error instead of
+    /// silently doing nothing.
+    pub fn validate_suppressions(&self) -> Result<(), String> {
+        for warning in &self.suppressed_warnings {
+            if !SUPPORTED_SUPPRESSED_WARNINGS.contains(&warning.as_str()) {
+                return Err(format!(
+                    "unsupported suppressed warning `{warning}`, expected one of {SUPPORTED_SUPPRESSED_WARNINGS:?}"
+                ));
+            }
+        }
+        for error in &self.suppressed_errors {
+            if !SUPPORTED_SUPPRESSED_ERRORS.contains(&error.as_str()) {
+                return Err(format!(
+                    "unsupported suppressed error `{error}`, expected one of {SUPPORTED_SUPPRESSED_ERRORS:?}"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a copy of these settings with `is_system`/`enable_eravm_extensions` reconciled
+    /// for the given `zksolc` version.
+    ///
+    /// zksolc versions below [`ERAVM_EXTENSIONS_MINIMUM_VERSION`] only understand the legacy
+    /// `isSystem` flag, while newer versions expect `enableEraVMExtensions` instead. This maps
+    /// whichever of the two flags the user set onto the key the target compiler actually reads,
+    /// so upgrading zksolc does not silently disable system-contract/EraVM-extension compilation.
+    pub fn normalized_for_version(&self, zksolc_version: &Version) -> Self {
+        let mut settings = self.clone();
+        if *zksolc_version >= ERAVM_EXTENSIONS_MINIMUM_VERSION {
+            if settings.is_system {
+                settings.enable_eravm_extensions = true;
+            }
+            settings.is_system = false;
+        } else {
+            if settings.enable_eravm_extensions {
+                settings.is_system = true;
+            }
+            settings.enable_eravm_extensions = false;
+        }
+        settings
+    }
+
+    /// Narrows `output_selection` down to [`MINIMAL_OUTPUT_SELECTION`] plus `required`.
+    ///
+    /// The full Solidity AST and every other artifact `solc`/`zksolc` can produce are expensive
+    /// to request and usually go unused; this keeps only what's needed to link and call a
+    /// contract (ABI and bytecode) plus whatever contract-level outputs the caller passes in
+    /// `required`. The resulting selection has no file-level entry, so the `ast` output is not
+    /// requested and cannot be added through this method.
+    pub fn prune_output_selection(&mut self, required: &[ContractOutputSelection]) {
+        let selectors = MINIMAL_OUTPUT_SELECTION
+            .iter()
+            .map(ToString::to_string)
+            .chain(required.iter().map(ToString::to_string));
+        self.output_selection = OutputSelection::common_output_selection(selectors);
+    }
+
+    /// Narrows `output_selection` down to just the ABI, for callers that only need to encode
+    /// calls and don't intend to deploy or link the contract.
+    pub fn abi_only_output_selection(&mut self) {
+        self.output_selection = OutputSelection::common_output_selection(["abi".to_string()]);
+    }
+
+    /// Sets the metadata hash mode (`none`/`ipfs`/`bzzr1`) appended to the bytecode.
+    ///
+    /// Pinning this to `BytecodeHash::None` is required for byte-identical, reproducible builds
+    /// across machines, since the IPFS/Swarm hashes otherwise embed a content hash of the
+    /// metadata itself.
+    pub fn set_bytecode_hash(&mut self, bytecode_hash: BytecodeHash) {
+        self.metadata
+            .get_or_insert_with(SettingsMetadata::default)
+            .bytecode_hash = Some(bytecode_hash);
+    }
 }
 
 impl Default for Settings {
@@ -94,10 +222,14 @@ impl Default for Settings {
             output_selection: OutputSelection::default_output_selection(),
             libraries: Default::default(),
             is_system: false,
+            enable_eravm_extensions: false,
             force_evmla: false,
             missing_libraries_path: None,
             are_libraries_missing: false,
             contracts_to_compile: Default::default(),
+            suppressed_warnings: Default::default(),
+            suppressed_errors: Default::default(),
+            other: Default::default(),
         }
     }
 }
@@ -111,8 +243,15 @@ impl Default for Settings {
 pub struct Optimizer {
     /// Whether the optimizer is enabled.
     pub enabled: Option<bool>,
-    /// The optimization mode string.
+    /// The optimization mode string, e.g. `"3"` for the most aggressive runtime-cost
+    /// optimization or `"z"` for the most aggressive code-size optimization.
     pub mode: Option<String>,
+    /// The number of times the deployed bytecode is expected to be executed, akin to `solc`'s
+    /// `--optimize-runs`. Low values favor cheaper deployment and smaller code, high values
+    /// favor cheaper repeated execution. Mutually exclusive with a `mode` of `"z"`, which always
+    /// targets minimal code size regardless of `runs`; see [`Optimizer::validate`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runs: Option<u32>,
     /// The `solc` optimizer details.
     pub details: Option<OptimizerDetails>,
     /// Whether to try to recompile with -Oz if the bytecode is too large.
@@ -122,6 +261,24 @@ pub struct Optimizer {
     #[serde(rename = "disableSystemRequestMemoization")]
     pub disable_system_request_memoization: bool,
 }
+
+impl Optimizer {
+    /// Validates that `mode` and `runs` are not set to a conflicting combination.
+    ///
+    /// zksolc's `"z"` mode always optimizes exclusively for code size, so a `runs` value set
+    /// alongside it would otherwise be silently ignored. Returning an error here surfaces that
+    /// at config time instead of producing a surprising compile result.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.runs.is_some() && self.mode.as_deref() == Some("z") {
+            return Err(
+                "`optimizer.mode = \"z\"` always optimizes for minimal code size and ignores `runs`; set only one of `mode` or `runs`"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
 /// A builder for `ZkSolcConfig`.
 #[derive(Default)]
 pub struct ZkSolcConfigBuilder {
@@ -146,9 +303,90 @@ impl ZkSolcConfigBuilder {
         self.settings = Some(settings);
         self
     }
+    /// Sets the `Optimizer` settings.
+    pub fn optimizer(mut self, optimizer: Optimizer) -> Self {
+        self.settings
+            .get_or_insert_with(Settings::default)
+            .optimizer = optimizer;
+        self
+    }
+    /// Sets the metadata settings.
+    pub fn metadata(mut self, metadata: SettingsMetadata) -> Self {
+        self.settings.get_or_insert_with(Settings::default).metadata = Some(metadata);
+        self
+    }
+    /// Sets the metadata hash mode (`none`/`ipfs`/`bzzr1`) appended to the bytecode. Pin this to
+    /// `BytecodeHash::None` in CI to get byte-identical artifacts across machines.
+    pub fn bytecode_hash(mut self, bytecode_hash: BytecodeHash) -> Self {
+        self.settings
+            .get_or_insert_with(Settings::default)
+            .set_bytecode_hash(bytecode_hash);
+        self
+    }
+    /// Sets the remappings to apply to the source files.
+    pub fn remappings(mut self, remappings: Vec<Remapping>) -> Self {
+        self.settings
+            .get_or_insert_with(Settings::default)
+            .remappings = remappings;
+        self
+    }
+    /// Sets the library addresses.
+    pub fn libraries(mut self, libraries: Libraries) -> Self {
+        self.settings
+            .get_or_insert_with(Settings::default)
+            .libraries = libraries;
+        self
+    }
+    /// Sets whether to enable the system contract compilation mode.
+    pub fn is_system(mut self, is_system: bool) -> Self {
+        self.settings
+            .get_or_insert_with(Settings::default)
+            .is_system = is_system;
+        self
+    }
+    /// Sets whether to forcibly switch to the EVM legacy assembly pipeline.
+    pub fn force_evmla(mut self, force_evmla: bool) -> Self {
+        self.settings
+            .get_or_insert_with(Settings::default)
+            .force_evmla = force_evmla;
+        self
+    }
+    /// Sets the output selection.
+    pub fn output_selection(mut self, output_selection: OutputSelection) -> Self {
+        self.settings
+            .get_or_insert_with(Settings::default)
+            .output_selection = output_selection;
+        self
+    }
+    /// Sets the list of contracts to compile.
+    pub fn contracts_to_compile(mut self, contracts_to_compile: Vec<String>) -> Self {
+        self.contracts_to_compile = Some(contracts_to_compile);
+        self
+    }
+    /// Sets the list of contracts to avoid compiling.
+    pub fn avoid_contracts(mut self, avoid_contracts: Vec<String>) -> Self {
+        self.avoid_contracts = Some(avoid_contracts);
+        self
+    }
     /// Builds the `ZkSolcConfig`.
     pub fn build(self) -> Result<ZkSolcConfig, String> {
         let settings = self.settings.unwrap_or_default();
+        settings.validate_suppressions()?;
+        settings.optimizer.validate()?;
+
+        if let (Some(contracts_to_compile), Some(avoid_contracts)) =
+            (&self.contracts_to_compile, &self.avoid_contracts)
+        {
+            if let Some(name) = contracts_to_compile
+                .iter()
+                .find(|name| avoid_contracts.contains(name))
+            {
+                return Err(format!(
+                    "`{name}` is present in both `contracts_to_compile` and `avoid_contracts`"
+                ));
+            }
+        }
+
         Ok(ZkSolcConfig {
             compiler_path: self.compiler_path,
             settings,
@@ -176,7 +414,58 @@ pub struct ZkStandardJsonCompilerInput {
 }
 impl ZkStandardJsonCompilerInput {
     /// Creates a new `ZkStandardJsonCompilerInput` instance with the specified parameters.
-    pub fn new(sources: Vec<(PathBuf, Source)>, settings: Settings) -> Self {
-        Self { language: SOLIDITY.to_string(), sources, settings }
+    ///
+    /// `settings` is reconciled for `zksolc_version` via [`Settings::normalized_for_version`]
+    /// before being embedded, so the emitted `isSystem`/`enableEraVMExtensions` always matches
+    /// what that compiler version actually reads. Building the struct directly from its public
+    /// fields, or deserializing it, skips that reconciliation.
+    pub fn new(
+        sources: Vec<(PathBuf, Source)>,
+        settings: Settings,
+        zksolc_version: &Version,
+    ) -> Self {
+        Self {
+            language: SOLIDITY.to_string(),
+            sources,
+            settings: settings.normalized_for_version(zksolc_version),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn flattened_settings_roundtrip_unknown_key() {
+        let mut settings = Settings::default();
+        settings
+            .other
+            .insert("newOptimizerKnob".to_string(), json!(true));
+
+        let serialized = serde_json::to_value(&settings).unwrap();
+        assert_eq!(serialized["newOptimizerKnob"], json!(true));
+
+        let deserialized: Settings = serde_json::from_value(serialized).unwrap();
+        assert_eq!(
+            deserialized.other.get("newOptimizerKnob"),
+            Some(&json!(true))
+        );
+    }
+
+    #[test]
+    fn flattened_settings_do_not_shadow_typed_fields() {
+        let settings = Settings {
+            is_system: true,
+            ..Default::default()
+        };
+
+        let serialized = serde_json::to_value(&settings).unwrap();
+        assert_eq!(serialized["isSystem"], json!(true));
+
+        let deserialized: Settings = serde_json::from_value(serialized).unwrap();
+        assert!(deserialized.is_system);
+        assert!(deserialized.other.is_empty());
     }
 }